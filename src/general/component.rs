@@ -7,5 +7,37 @@ pub enum Component {
     /// Resistors.
     Resistor { pin1: usize, pin2: usize, r: f64 },
     /// Diodes with an exponential current–voltage characteristic.
-    Diode { anode: usize, cathode: usize, i_s: f64, n: f64 }
+    Diode { anode: usize, cathode: usize, i_s: f64, n: f64 },
+    /// Capacitors: only meaningful to [`crate::simulation::tran::simulate_tran`], which
+    /// replaces them with a companion conductance and current source at each time step.
+    Capacitor { pin1: usize, pin2: usize, c: f64 },
+    /// Inductors: only meaningful to [`crate::simulation::tran::simulate_tran`], which
+    /// replaces them with a companion conductance and current source at each time step.
+    Inductor { pin1: usize, pin2: usize, l: f64 },
+    /// A bipolar junction transistor, modeled with the injection-form Ebers–Moll equations.
+    /// Couples three nodes through two controlling junction voltages, so it's only handled
+    /// by the MNA/Newton backend ([`crate::simulation::op::simulate_op_mna`]), which linearizes
+    /// it with a 3x3 Jacobian stamp each iteration.
+    Bjt { collector: usize, base: usize, emitter: usize, is: f64, bf: f64, br: f64, n: f64, npn: bool },
+    /// A level-1 (Shichman–Hodges square-law) MOSFET. Like [`Component::Bjt`], it couples
+    /// three nodes — though the gate draws no DC current, so only the drain/source KCL
+    /// rows need a Jacobian stamp — and is only handled by the MNA/Newton backend.
+    Mosfet { drain: usize, gate: usize, source: usize, kp: f64, vth: f64, lambda: f64, nchan: bool },
+    /// Voltage-controlled current source: injects `gm*(V[ctrl_p]-V[ctrl_n])` into `out_p`/`out_n`.
+    /// The only controlled source simple enough to need no branch-current unknown, so — unlike
+    /// [`Component::Vcvs`], [`Component::Cccs`], and [`Component::Ccvs`] — it's supported by both
+    /// [`crate::simulation::op::simulate_op`] and [`crate::simulation::op::simulate_op_mna`].
+    Vccs { out_p: usize, out_n: usize, ctrl_p: usize, ctrl_n: usize, gm: f64 },
+    /// Voltage-controlled voltage source: enforces `V[out_p]-V[out_n] = e*(V[ctrl_p]-V[ctrl_n])`.
+    /// Needs a branch-current unknown, so it's only handled by [`crate::simulation::op::simulate_op_mna`].
+    Vcvs { out_p: usize, out_n: usize, ctrl_p: usize, ctrl_n: usize, e: f64 },
+    /// Current-controlled current source: injects `beta` times the branch current of another
+    /// branch-creating component (a [`Component::VoltageDc`], [`Component::Vcvs`], or
+    /// [`Component::Ccvs`]) into `out_p`/`out_n`. `controlling` is that component's index in
+    /// `circuit.components`. Only handled by [`crate::simulation::op::simulate_op_mna`].
+    Cccs { out_p: usize, out_n: usize, controlling: usize, beta: f64 },
+    /// Current-controlled voltage source: enforces `V[out_p]-V[out_n] = r*I[controlling]`, where
+    /// `controlling` names another branch-creating component's index in `circuit.components`
+    /// the same way [`Component::Cccs`] does. Only handled by [`crate::simulation::op::simulate_op_mna`].
+    Ccvs { out_p: usize, out_n: usize, controlling: usize, r: f64 }
 }
\ No newline at end of file
@@ -1,4 +1,34 @@
-use std::f64::consts::E;
+use std::f64::consts::{E, SQRT_2};
+
+/// Thermal voltage `n*Vt` for the given ideality factor `n`, at room temperature.
+fn n_vt(n: f64) -> f64 {
+    n * 0.025852
+}
+
+/// SPICE-style pn-junction voltage limiting.
+///
+/// Rather than hard-clamping a diode's junction voltage to some fixed range (which distorts
+/// the I–V curve and masks convergence failures), this only steps in when the proposed
+/// voltage `vnew` lies above the critical voltage `vcrit = n*Vt*ln(n*Vt/(sqrt(2)*Is))` and
+/// differs from the previous junction voltage `vold` by more than `2*n*Vt`. The step from
+/// `vold` is then compressed logarithmically to `vold + n*Vt*ln(1 + (vnew - vold)/(n*Vt))`,
+/// or, where that logarithm's argument is not positive (a large downward step), the result
+/// is `vcrit`. In every other case `vnew` is returned unchanged, so the true exponential
+/// characteristic is left intact.
+pub(crate) fn limit_junction_voltage(vnew: f64, vold: f64, n: f64, i_s: f64) -> f64 {
+    let n_vt = n_vt(n);
+    let vcrit = n_vt * (n_vt / (SQRT_2 * i_s)).ln();
+    if vnew > vcrit && (vnew - vold).abs() > 2.0 * n_vt {
+        let arg = 1.0 + (vnew - vold) / n_vt;
+        if arg > 0.0 {
+            vold + n_vt * arg.ln()
+        } else {
+            vcrit
+        }
+    } else {
+        vnew
+    }
+}
 
 /// Enum representing different roles (i.e. component contributions) for a node.
 #[derive(Clone, Debug)]
@@ -23,9 +53,11 @@ impl Role {
     /// Computes the contribution of this role to the “virtual charge” at the target node.
     ///
     /// * For resistors, this is simply the voltage difference multiplied by conductance.
-    /// * For diodes, an exponential current–voltage characteristic is used.
+    /// * For diodes, an exponential current–voltage characteristic is used, with the proposed
+    ///   junction voltage pn-junction limited against its value in `prev_voltages` (see
+    ///   [`limit_junction_voltage`]) instead of being hard-clamped.
     /// * Constant charges inject a fixed amount.
-    pub fn q_vir_impact(&self, voltages: &[f64], target_node: usize) -> f64 {
+    pub fn q_vir_impact(&self, voltages: &[f64], prev_voltages: &[f64], target_node: usize) -> f64 {
         match *self {
             Role::ConstantCharge(current) => current,
             Role::Linear { conductance, neighbor } => {
@@ -33,10 +65,10 @@ impl Role {
                 conductance * (voltages[neighbor] - voltages[target_node])
             }
             Role::Exponential { i_s, n, neighbor: _, anode, cathode, flip } => {
-                // Compute diode current with voltage clamping to avoid overflow.
-                let v_diff = (voltages[anode] - voltages[cathode])
-                    .clamp(-5.0, 5.0);
-                flip * i_s * (E.powf(v_diff / (n * 0.025852)) - 1.0)
+                let v_raw = voltages[anode] - voltages[cathode];
+                let v_old = prev_voltages[anode] - prev_voltages[cathode];
+                let v_diff = limit_junction_voltage(v_raw, v_old, n, i_s);
+                flip * i_s * (E.powf(v_diff / n_vt(n)) - 1.0)
             }
         }
     }
@@ -61,6 +93,7 @@ impl Role {
 
 pub fn q_vir_impact(
     voltages: &[f64],
+    prev_voltages: &[f64],
     consts: &ConstantCharges,
     linears: &LinearContributions,
     exps: &ExponentialContributions
@@ -80,9 +113,10 @@ pub fn q_vir_impact(
     }
 
     for i in 0..exps.i_s.len() {
-        let v_diff = (voltages[exps.anodes[i]] - voltages[exps.cathodes[i]])
-            .clamp(-5.0, 5.0);
-        let current = exps.flips[i] * exps.i_s[i] * (E.powf(v_diff / (exps.n[i] * 0.025852)) - 1.0);
+        let v_raw = voltages[exps.anodes[i]] - voltages[exps.cathodes[i]];
+        let v_old = prev_voltages[exps.anodes[i]] - prev_voltages[exps.cathodes[i]];
+        let v_diff = limit_junction_voltage(v_raw, v_old, exps.n[i], exps.i_s[i]);
+        let current = exps.flips[i] * exps.i_s[i] * (E.powf(v_diff / n_vt(exps.n[i])) - 1.0);
         let target = exps.target_nodes[i];
         q_vir[target] += current;
     }
@@ -144,4 +178,46 @@ pub struct ExponentialContributions {
     pub cathodes: Vec<usize>,
     pub flips: Vec<f64>,
     pub target_nodes: Vec<usize>,
+}
+
+/// All bipolar junction transistors, modeled with the injection-form Ebers–Moll equations.
+///
+/// Unlike [`ExponentialContributions`], a BJT couples three nodes through two controlling
+/// junction voltages, so each entry carries all three terminals plus the forward/reverse
+/// current gains needed to linearize it into a 3x3 Jacobian stamp.
+pub struct BjtContributions {
+    pub collectors: Vec<usize>,
+    pub bases: Vec<usize>,
+    pub emitters: Vec<usize>,
+    pub i_s: Vec<f64>,
+    pub bf: Vec<f64>,
+    pub br: Vec<f64>,
+    pub n: Vec<f64>,
+    pub npn: Vec<bool>,
+}
+
+/// All level-1 (Shichman–Hodges) MOSFETs. Shares the same multi-terminal shape as
+/// [`BjtContributions`] — three terminals linearized into a small Jacobian stamp each
+/// Newton iteration — though here the gate never carries current.
+pub struct MosfetContributions {
+    pub drains: Vec<usize>,
+    pub gates: Vec<usize>,
+    pub sources: Vec<usize>,
+    pub kp: Vec<f64>,
+    pub vth: Vec<f64>,
+    pub lambda: Vec<f64>,
+    pub nchan: Vec<bool>,
+}
+
+/// All voltage-controlled current sources. Unlike the other controlled sources, a VCCS needs
+/// no branch-current unknown — its current depends only on two *other* nodes' voltages — so
+/// it's the one controlled source [`crate::simulation::op::simulate_op`]'s relaxation loop can
+/// also represent, injected the same way [`ConstantCharges`] is but recomputed every iteration
+/// from the controlling nodes' voltages rather than held fixed.
+pub struct VccsContributions {
+    pub gm: Vec<f64>,
+    pub ctrl_p: Vec<usize>,
+    pub ctrl_n: Vec<usize>,
+    pub flips: Vec<f64>,
+    pub target_nodes: Vec<usize>,
 }
\ No newline at end of file
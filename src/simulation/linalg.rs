@@ -0,0 +1,100 @@
+//! Small dense linear algebra helpers used by the MNA-based solvers.
+//!
+//! The matrices involved never grow beyond a few hundred unknowns for the
+//! circuits this crate targets, so a minimal in-crate implementation is
+//! used instead of pulling in an external linear algebra dependency.
+
+/// A dense, row-major matrix of `f64` values.
+#[derive(Clone, Debug)]
+pub struct Matrix {
+    pub rows: usize,
+    pub cols: usize,
+    pub data: Vec<f64>,
+}
+
+impl Matrix {
+    /// Creates a `rows x cols` matrix filled with zeros.
+    pub fn zeros(rows: usize, cols: usize) -> Self {
+        Matrix { rows, cols, data: vec![0.0; rows * cols] }
+    }
+
+    #[inline]
+    pub fn get(&self, r: usize, c: usize) -> f64 {
+        self.data[r * self.cols + c]
+    }
+
+    #[inline]
+    pub fn set(&mut self, r: usize, c: usize, v: f64) {
+        self.data[r * self.cols + c] = v;
+    }
+
+    /// Adds `v` to the existing entry at `(r, c)` — the usual way MNA stamps
+    /// are applied, since several components may contribute to the same cell.
+    #[inline]
+    pub fn add(&mut self, r: usize, c: usize, v: f64) {
+        self.data[r * self.cols + c] += v;
+    }
+}
+
+/// Solves the square linear system `a * x = b`, returning the solution
+/// vector, or `None` if `a` is singular (to working precision).
+///
+/// Uses Gaussian elimination with partial pivoting, factoring `a` into `L`
+/// and `U` in place and applying the row operations to `b` as it goes,
+/// followed by back substitution.
+pub fn lu_solve(a: &mut Matrix, b: &mut [f64]) -> Option<Vec<f64>> {
+    let n = a.rows;
+    debug_assert_eq!(a.cols, n);
+    debug_assert_eq!(b.len(), n);
+
+    for k in 0..n {
+        // Partial pivoting: bring the largest-magnitude entry in column k onto the diagonal.
+        let mut max_row = k;
+        let mut max_val = a.get(k, k).abs();
+        for i in (k + 1)..n {
+            let v = a.get(i, k).abs();
+            if v > max_val {
+                max_val = v;
+                max_row = i;
+            }
+        }
+
+        if max_val < 1e-300 {
+            return None;
+        }
+
+        if max_row != k {
+            for c in 0..n {
+                let tmp = a.get(k, c);
+                a.set(k, c, a.get(max_row, c));
+                a.set(max_row, c, tmp);
+            }
+            b.swap(k, max_row);
+        }
+
+        let pivot = a.get(k, k);
+        for i in (k + 1)..n {
+            let factor = a.get(i, k) / pivot;
+            if factor == 0.0 {
+                continue;
+            }
+            for c in (k + 1)..n {
+                let v = a.get(i, c) - factor * a.get(k, c);
+                a.set(i, c, v);
+            }
+            b[i] -= factor * b[k];
+        }
+    }
+
+    // Back substitution: `a` now holds U (the L multipliers were only needed above).
+    let mut x = vec![0.0; n];
+    for i in (0..n).rev() {
+        let mut sum = b[i];
+        for (c, &xc) in x.iter().enumerate().skip(i + 1) {
+            sum -= a.get(i, c) * xc;
+        }
+        x[i] = sum / a.get(i, i);
+    }
+
+    Some(x)
+}
@@ -2,5 +2,6 @@
 //!
 //! This module provides various simulation algorithms for analyzing circuits.
 
+pub mod linalg;
 pub mod op;
-pub mod role;
\ No newline at end of file
+pub mod tran;
\ No newline at end of file
@@ -1,6 +1,12 @@
 use std::f64::consts::E;
 use crate::general::circuit::{Circuit, Component};
-use crate::general::role::{ConstantCharges, ExponentialContributions, LinearContributions};
+use crate::general::role::{limit_junction_voltage, ConstantCharges, ExponentialContributions, LinearContributions, VccsContributions};
+use crate::simulation::linalg::{lu_solve, Matrix};
+
+/// The nominal minimum conductance added from every non-ground node to ground (gmin stepping).
+const GMIN_NOMINAL: f64 = 1e-12;
+/// The starting conductance used when the relaxation loop fails to converge at `GMIN_NOMINAL`.
+const GMIN_START: f64 = 1e-3;
 
 /// Simulates the operating point of the circuit by iteratively updating node voltages.
 ///
@@ -22,6 +28,14 @@ use crate::general::role::{ConstantCharges, ExponentialContributions, LinearCont
 /// 3. Adapts the damping factor based on whether the error is decreasing or increasing.
 /// 4. Checks for convergence.
 /// 5. Normalizes the node voltages relative to the ground node.
+///
+/// Every non-ground node also carries a small `gmin` conductance to ground. Normally this
+/// sits at `GMIN_NOMINAL` and has no visible effect; if the loop fails to converge, it's
+/// raised to `GMIN_START` and geometrically stepped back down by a factor of 10 each time
+/// the inner loop converges, which is the standard SPICE trick for coaxing convergence out
+/// of badly-conditioned nonlinear circuits. Diode branches are also pn-junction limited
+/// (see [`limit_junction_voltage`]) instead of having their voltage hard-clamped, so the
+/// true exponential characteristic is preserved far outside of `[-5, 5]` V.
 pub fn simulate_op(
     circuit: &Circuit,
     t_vir: f64,
@@ -29,12 +43,12 @@ pub fn simulate_op(
     initial_voltages: Option<Vec<f64>>,
 ) -> Vec<f64> {
     let mut voltages = initial_voltages.unwrap_or_else(|| vec![0.0; circuit.nodes_count]);
-    let mut charges = vec![0.0; circuit.nodes_count];
 
     // --- Preprocessing: collect component contributions ---
     let mut consts = ConstantCharges { currents: vec![], target_nodes: vec![] };
     let mut linears = LinearContributions { conductances: vec![], neighbors: vec![], target_nodes: vec![] };
     let mut exps = ExponentialContributions { i_s: vec![], n: vec![], anodes: vec![], cathodes: vec![], flips: vec![], target_nodes: vec![], };
+    let mut vccs = VccsContributions { gm: vec![], ctrl_p: vec![], ctrl_n: vec![], flips: vec![], target_nodes: vec![] };
 
     for component in &circuit.components { match component {
 
@@ -75,16 +89,131 @@ pub fn simulate_op(
             exps.target_nodes.push(*cathode);
         }
 
+        Component::Capacitor { .. } | Component::Inductor { .. } => {
+            // Reactive elements aren't representable in this relaxation scheme; use
+            // `simulation::tran::simulate_tran` for circuits that contain them.
+        }
+
+        Component::Bjt { .. } | Component::Mosfet { .. } => {
+            // Three-terminal devices need a Jacobian stamp, not a single role per node;
+            // use `simulate_op_mna` for circuits that contain them.
+        }
+
+        Component::Vccs { out_p, out_n, ctrl_p, ctrl_n, gm } => {
+            let scaled_gm = gm * t_vir;
+            vccs.gm.push(scaled_gm);
+            vccs.ctrl_p.push(*ctrl_p);
+            vccs.ctrl_n.push(*ctrl_n);
+            vccs.flips.push(1.0);
+            vccs.target_nodes.push(*out_p);
+
+            vccs.gm.push(scaled_gm);
+            vccs.ctrl_p.push(*ctrl_p);
+            vccs.ctrl_n.push(*ctrl_n);
+            vccs.flips.push(-1.0);
+            vccs.target_nodes.push(*out_n);
+        }
+
+        Component::Vcvs { .. } | Component::Cccs { .. } | Component::Ccvs { .. } => {
+            // These need a branch-current unknown, which this relaxation scheme has no room
+            // for; use `simulate_op_mna` for circuits that contain them.
+        }
+
     } }
 
-    // --- Simulation parameters ---
+    let contributions = Contributions { consts: &consts, linears: &linears, exps: &exps, vccs: &vccs };
+
+    // pn-junction limiting state: the previous junction voltage for each entry in `exps`.
+    let mut vold = vec![0.0; exps.i_s.len()];
+    let mut state = GminState { gmin: GMIN_NOMINAL, vold: &mut vold };
+
+    // --- gmin stepping: try the nominal gmin first, only stepping it up if that fails ---
+    loop {
+        let (result, converged) = relax(circuit, tolerance, &contributions, voltages, &mut state);
+        voltages = result;
+
+        if converged {
+            if state.gmin <= GMIN_NOMINAL {
+                break;
+            }
+            state.gmin = (state.gmin / 10.0).max(GMIN_NOMINAL);
+        } else if state.gmin < GMIN_START {
+            state.gmin = GMIN_START;
+        } else {
+            // Even the heavily gmin-damped system failed to converge; report what we have.
+            break;
+        }
+    }
+
+    // --- Normalize to ground ---
+    let gnd_v = voltages[circuit.ground_node];
+    for v in &mut voltages {
+        *v -= gnd_v;
+    }
+
+    voltages
+}
+
+/// The preprocessed per-component contributions `relax` draws on every iteration, bundled
+/// together since they're always built and passed as a unit (see `simulate_op`'s preprocessing
+/// step).
+struct Contributions<'a> {
+    consts: &'a ConstantCharges,
+    linears: &'a LinearContributions,
+    exps: &'a ExponentialContributions,
+    vccs: &'a VccsContributions,
+}
+
+/// Gmin-stepping state threaded through `relax`: the gmin value for this call, plus the
+/// pn-junction-limiting `vold` (the previous junction voltage for each entry of `exps`)
+/// carried across calls so that limiting stays continuous as the caller steps `gmin` down.
+struct GminState<'a> {
+    gmin: f64,
+    vold: &'a mut [f64],
+}
+
+/// Runs the relaxation loop to (attempted) convergence at a fixed `gmin`, returning the
+/// resulting voltages and whether `tolerance` was reached before `max_iterations`.
+fn relax(
+    circuit: &Circuit,
+    tolerance: f64,
+    contributions: &Contributions,
+    mut voltages: Vec<f64>,
+    state: &mut GminState,
+) -> (Vec<f64>, bool) {
+    let &Contributions { consts, linears: base_linears, exps, vccs } = contributions;
+    let gmin = state.gmin;
+    let vold = &mut *state.vold;
+
+    let gnd = circuit.ground_node;
+    let mut charges = vec![0.0; circuit.nodes_count];
+
+    // `gmin` itself is just another linear (resistive) contribution, from every non-ground
+    // node to ground, layered on top of the resistor-derived ones.
+    let mut linears = LinearContributions {
+        conductances: base_linears.conductances.clone(),
+        neighbors: base_linears.neighbors.clone(),
+        target_nodes: base_linears.target_nodes.clone(),
+    };
+    for node in 0..circuit.nodes_count {
+        if node == gnd {
+            continue;
+        }
+        linears.conductances.push(gmin);
+        linears.neighbors.push(gnd);
+        linears.target_nodes.push(node);
+        linears.conductances.push(gmin);
+        linears.neighbors.push(node);
+        linears.target_nodes.push(gnd);
+    }
+
     let max_iterations = 10_000;
     let mut iteration = 0;
     let mut damper = 1.0;
     let mut prev_voltages = voltages.clone();
     let mut prev_error = f64::INFINITY;
+    let mut converged = false;
 
-    // --- Simulation loop ---
     loop {
         charges.fill(0.0);
 
@@ -98,10 +227,17 @@ pub fn simulate_op(
             charges[t] += linears.conductances[i] * (voltages[n] - voltages[t]);
         }
         for i in 0..exps.i_s.len() {
-            let v_diff = (voltages[exps.anodes[i]] - voltages[exps.cathodes[i]]).clamp(-5.0, 5.0);
-            let current = exps.flips[i] * exps.i_s[i] * (E.powf(v_diff / (exps.n[i] * 0.025852)) - 1.0);
+            let n_vt = exps.n[i] * 0.025852;
+            let v_raw = voltages[exps.anodes[i]] - voltages[exps.cathodes[i]];
+            let v_diff = limit_junction_voltage(v_raw, vold[i], exps.n[i], exps.i_s[i]);
+            vold[i] = v_diff;
+            let current = exps.flips[i] * exps.i_s[i] * (E.powf(v_diff / n_vt) - 1.0);
             charges[exps.target_nodes[i]] += current;
         }
+        for i in 0..vccs.gm.len() {
+            let current = vccs.flips[i] * vccs.gm[i] * (voltages[vccs.ctrl_p[i]] - voltages[vccs.ctrl_n[i]]);
+            charges[vccs.target_nodes[i]] += current;
+        }
 
         // Compute voltage updates
         let mut delta_vs = vec![0.0; circuit.nodes_count];
@@ -137,19 +273,360 @@ pub fn simulate_op(
         iteration += 1;
 
         if max_delta_v < tolerance {
-            println!("Converged in {} iterations with damper {:.3}", iteration, damper);
+            println!("Converged in {} iterations with damper {:.3} (gmin {:.1e})", iteration, damper, gmin);
+            converged = true;
             break;
         }
         if iteration >= max_iterations {
-            println!("Warning: Max iterations reached without convergence.");
+            println!("Warning: max iterations reached without convergence (gmin {:.1e}).", gmin);
             break;
         }
     }
 
-    // --- Normalize to ground ---
-    let gnd_v = voltages[circuit.ground_node];
-    for v in &mut voltages {
-        *v -= gnd_v;
+    (voltages, converged)
+}
+
+/// Simulates the operating point of the circuit using Modified Nodal Analysis.
+///
+/// Unlike [`simulate_op`], which relies on an iterative relaxation scheme and
+/// cannot represent an ideal voltage source or a branch-current unknown, this
+/// solver assembles the classic `(n + m) x (n + m)` MNA system — `n`
+/// node-voltage unknowns plus one branch-current unknown `m` per
+/// voltage-defining component ([`Component::VoltageDc`], [`Component::Vcvs`],
+/// [`Component::Ccvs`]) — and solves it directly with LU factorization, after
+/// deleting the ground node's row and column (pinning it to `0 V`). This is
+/// also the only solver that handles [`Component::Bjt`], [`Component::Mosfet`],
+/// and the controlled sources ([`Component::Vccs`], [`Component::Vcvs`],
+/// [`Component::Cccs`], [`Component::Ccvs`]).
+///
+/// Diodes and BJT junctions are nonlinear, so they are linearized into a Norton
+/// companion model at each Newton–Raphson iteration: `geq = (i_s/(n*Vt))*exp(v/(n*Vt))`
+/// stamped like a resistor conductance, and `ieq = i(v) - geq*v` stamped
+/// like a current source. The proposed junction voltage is pn-junction limited
+/// against its value from the previous Newton iteration (see
+/// [`limit_junction_voltage`]) rather than hard-clamped, the same way
+/// [`simulate_op`]'s relaxation loop handles it. The system is re-assembled and
+/// re-solved from the latest voltage guess until the largest node-voltage change
+/// between iterations drops below `tolerance` and every limited junction voltage
+/// has caught up with its unlimited value to within `tolerance`.
+///
+/// # Parameters
+/// - `circuit`: The circuit description.
+/// - `tolerance`: The Newton-iteration convergence threshold (maximum allowed node-voltage change).
+/// - `initial_voltages`: Optional initial guess for node voltages (if `None`, start at zero).
+///
+/// # Returns
+/// A vector of node voltages, normalized relative to the ground node.
+pub fn simulate_op_mna(
+    circuit: &Circuit,
+    tolerance: f64,
+    initial_voltages: Option<Vec<f64>>,
+) -> Vec<f64> {
+    let voltages = initial_voltages.unwrap_or_else(|| vec![0.0; circuit.nodes_count]);
+    newton_mna(circuit, tolerance, voltages, &[])
+}
+
+/// An extra conductance `g` between `pin1` and `pin2`, plus a current source
+/// `i_into_pin1` flowing into `pin1` (and out of `pin2`) — a Norton companion
+/// model. Used by [`crate::simulation::tran::simulate_tran`] to inject the
+/// per-time-step capacitor/inductor companions into the MNA system without
+/// duplicating the Newton machinery below.
+pub(crate) struct ExtraStamp {
+    pub pin1: usize,
+    pub pin2: usize,
+    pub g: f64,
+    pub i_into_pin1: f64,
+}
+
+/// Shared Newton/MNA core behind [`simulate_op_mna`] and
+/// [`crate::simulation::tran::simulate_tran`]: assembles the `(n + m) x (n + m)`
+/// MNA system from `circuit`'s components plus any `extra_stamps`, linearizing
+/// diodes with a Norton companion model, and re-solves from `initial_voltages`
+/// until the largest node-voltage change between iterations drops below
+/// `tolerance` and every pn-limited junction voltage is within `tolerance` of
+/// its unlimited value (or the iteration budget is exhausted).
+pub(crate) fn newton_mna(
+    circuit: &Circuit,
+    tolerance: f64,
+    initial_voltages: Vec<f64>,
+    extra_stamps: &[ExtraStamp],
+) -> Vec<f64> {
+    let n = circuit.nodes_count;
+    let gnd = circuit.ground_node;
+
+    // Components that create their own branch-current unknown: ideal voltage sources plus
+    // the two controlled sources ([`Component::Vcvs`], [`Component::Ccvs`]) that enforce a
+    // voltage relationship rather than injecting a current directly.
+    let branch_indices: Vec<usize> = circuit.components.iter().enumerate()
+        .filter_map(|(i, c)| matches!(c, Component::VoltageDc { .. } | Component::Vcvs { .. } | Component::Ccvs { .. }).then_some(i))
+        .collect();
+    let m = branch_indices.len();
+    let dim = n + m;
+
+    let mut voltages = initial_voltages;
+
+    // pn-junction limiting state (see `limit_junction_voltage`), carried across Newton
+    // iterations the same way `relax` carries `vold` across gmin steps. Indexed by each
+    // diode's/BJT's own position in `circuit.components`; a BJT needs one entry per junction.
+    let mut vold_diode = vec![0.0; circuit.components.len()];
+    let mut vold_vbe = vec![0.0; circuit.components.len()];
+    let mut vold_vbc = vec![0.0; circuit.components.len()];
+
+    let max_newton_iterations = 100;
+    for _ in 0..max_newton_iterations {
+        let mut mat = Matrix::zeros(dim, dim);
+        let mut rhs = vec![0.0; dim];
+
+        // Largest gap between a junction's pn-limited voltage and its unlimited target this
+        // iteration. While a junction is still ramping through its limited steps, the node
+        // voltages driving it can stop moving (the limited current barely changes them) long
+        // before the junction itself has caught up to its true operating point — so this has
+        // to gate convergence alongside `max_delta_v` below, not just the node voltages.
+        let mut max_junction_residual: f64 = 0.0;
+
+        // Looks up the MNA row/column assigned to a branch-creating component's current unknown.
+        let branch_row = |component_idx: usize| -> usize {
+            n + branch_indices.iter().position(|&i| i == component_idx)
+                .expect("controlling/defining component must create its own branch-current unknown")
+        };
+
+        for (idx, component) in circuit.components.iter().enumerate() {
+            match component {
+                Component::VoltageDc { anode, cathode, v } => {
+                    let row = branch_row(idx);
+                    mat.add(*anode, row, 1.0);
+                    mat.add(row, *anode, 1.0);
+                    mat.add(*cathode, row, -1.0);
+                    mat.add(row, *cathode, -1.0);
+                    rhs[row] = *v;
+                }
+
+                Component::CurrentDc { anode, cathode, current } => {
+                    rhs[*anode] += current;
+                    rhs[*cathode] -= current;
+                }
+
+                Component::Resistor { pin1, pin2, r } => {
+                    let g = 1.0 / r;
+                    mat.add(*pin1, *pin1, g);
+                    mat.add(*pin2, *pin2, g);
+                    mat.add(*pin1, *pin2, -g);
+                    mat.add(*pin2, *pin1, -g);
+                }
+
+                Component::Diode { anode, cathode, i_s, n: ideality } => {
+                    let vt = ideality * 0.025852;
+                    let v_raw = voltages[*anode] - voltages[*cathode];
+                    let v = limit_junction_voltage(v_raw, vold_diode[idx], *ideality, *i_s);
+                    max_junction_residual = max_junction_residual.max((v_raw - v).abs());
+                    vold_diode[idx] = v;
+                    let exp_v = E.powf(v / vt);
+                    let id = i_s * (exp_v - 1.0);
+                    let geq = (i_s / vt) * exp_v;
+                    let ieq = id - geq * v;
+
+                    mat.add(*anode, *anode, geq);
+                    mat.add(*cathode, *cathode, geq);
+                    mat.add(*anode, *cathode, -geq);
+                    mat.add(*cathode, *anode, -geq);
+
+                    rhs[*anode] -= ieq;
+                    rhs[*cathode] += ieq;
+                }
+
+                Component::Capacitor { .. } => {
+                    // Open circuit absent a companion model; `simulate_tran` supplies one via `extra_stamps`.
+                }
+
+                Component::Inductor { pin1, pin2, .. } => {
+                    // Short circuit absent a companion model; `simulate_tran` supplies one via `extra_stamps`
+                    // (it passes a stamp for every capacitor and inductor), so the short is left out then.
+                    // Approximated with a large conductance rather than a branch equation, since an exact
+                    // short isn't needed outside of transient stepping.
+                    if extra_stamps.is_empty() {
+                        let g = 1e9;
+                        mat.add(*pin1, *pin1, g);
+                        mat.add(*pin2, *pin2, g);
+                        mat.add(*pin1, *pin2, -g);
+                        mat.add(*pin2, *pin1, -g);
+                    }
+                }
+
+                Component::Bjt { collector, base, emitter, is, bf, br, n, npn } => {
+                    // Injection-form Ebers-Moll: two coupled pn junctions (base-emitter,
+                    // base-collector) drive a transport current between collector and emitter.
+                    // PNP devices are handled by flipping the controlling voltages' polarity and
+                    // flipping the resulting currents back — the Jacobian is identical either way.
+                    let pol = if *npn { 1.0 } else { -1.0 };
+                    let vt = n * 0.025852;
+                    let vbe_raw = pol * (voltages[*base] - voltages[*emitter]);
+                    let vbe = limit_junction_voltage(vbe_raw, vold_vbe[idx], *n, *is);
+                    max_junction_residual = max_junction_residual.max((vbe_raw - vbe).abs());
+                    vold_vbe[idx] = vbe;
+                    let vbc_raw = pol * (voltages[*base] - voltages[*collector]);
+                    let vbc = limit_junction_voltage(vbc_raw, vold_vbc[idx], *n, *is);
+                    max_junction_residual = max_junction_residual.max((vbc_raw - vbc).abs());
+                    vold_vbc[idx] = vbc;
+
+                    let xf = E.powf(vbe / vt);
+                    let xr = E.powf(vbc / vt);
+
+                    let ibe = (is / bf) * (xf - 1.0);
+                    let ibc = (is / br) * (xr - 1.0);
+                    let ict = is * (xf - xr);
+
+                    let gbe = (is / (bf * vt)) * xf;
+                    let gbc = (is / (br * vt)) * xr;
+                    let gif = (is / vt) * xf;
+                    let gir = (is / vt) * xr;
+
+                    let ib = ibe + ibc;
+                    let ic = ict - ibc;
+                    let ie = -(ib + ic);
+
+                    // Rows/cols ordered (base, collector, emitter); derivatives are independent of `pol`.
+                    let nodes = [*base, *collector, *emitter];
+                    let currents = [pol * ib, pol * ic, pol * ie];
+                    let jacobian = [
+                        [gbe + gbc, -gbc, -gbe],
+                        [gif - gir - gbc, gbc + gir, -gif],
+                        [-(gbe + gif - gir), -gir, gbe + gif],
+                    ];
+                    let v_op = [voltages[*base], voltages[*collector], voltages[*emitter]];
+
+                    for row in 0..3 {
+                        let mut ieq = currents[row];
+                        for col in 0..3 {
+                            mat.add(nodes[row], nodes[col], jacobian[row][col]);
+                            ieq -= jacobian[row][col] * v_op[col];
+                        }
+                        rhs[nodes[row]] -= ieq;
+                    }
+                }
+
+                Component::Mosfet { drain, gate, source, kp, vth, lambda, nchan } => {
+                    // Level-1 (Shichman-Hodges) square-law model. PMOS is handled the same way as
+                    // the BJT's PNP case: flip the controlling voltages' polarity, solve as if NMOS,
+                    // then flip the resulting drain current back.
+                    let pol = if *nchan { 1.0 } else { -1.0 };
+                    let vgs = pol * (voltages[*gate] - voltages[*source]);
+                    let vds = pol * (voltages[*drain] - voltages[*source]);
+                    let vov = vgs - vth;
+
+                    let (id, gm, gds) = if vov <= 0.0 {
+                        (0.0, 0.0, 0.0)
+                    } else if vds < vov {
+                        // Triode/linear region.
+                        let id = kp * (vov * vds - vds * vds / 2.0) * (1.0 + lambda * vds);
+                        let gm = kp * vds * (1.0 + lambda * vds);
+                        let gds = kp * (vov - vds) * (1.0 + lambda * vds) + kp * (vov * vds - vds * vds / 2.0) * lambda;
+                        (id, gm, gds)
+                    } else {
+                        // Saturation region.
+                        let id = 0.5 * kp * vov * vov * (1.0 + lambda * vds);
+                        let gm = kp * vov * (1.0 + lambda * vds);
+                        let gds = 0.5 * kp * vov * vov * lambda;
+                        (id, gm, gds)
+                    };
+
+                    let id_actual = pol * id;
+
+                    // Only the drain/source KCL rows need a stamp; the gate draws no DC current.
+                    let cols = [(*drain, gds), (*gate, gm), (*source, -(gm + gds))];
+                    for (row_node, row_current) in [(*drain, id_actual), (*source, -id_actual)] {
+                        let sign = if row_node == *drain { 1.0 } else { -1.0 };
+                        let mut ieq = row_current;
+                        for &(col_node, g) in &cols {
+                            let g = sign * g;
+                            mat.add(row_node, col_node, g);
+                            ieq -= g * voltages[col_node];
+                        }
+                        rhs[row_node] -= ieq;
+                    }
+                }
+
+                Component::Vccs { out_p, out_n, ctrl_p, ctrl_n, gm } => {
+                    // No branch-current unknown needed: the controlled current is a direct
+                    // function of two other nodes' voltages, so it stamps like a (non-reciprocal)
+                    // conductance between the output and controlling pairs. The injected current
+                    // (entering `out_p`, leaving `out_n`, same convention as `Component::CurrentDc`)
+                    // depends on unknowns rather than being constant, so it's moved from the rhs
+                    // to the matrix with a sign flip, same as `Component::Cccs` below.
+                    mat.add(*out_p, *ctrl_p, -*gm);
+                    mat.add(*out_p, *ctrl_n, *gm);
+                    mat.add(*out_n, *ctrl_p, *gm);
+                    mat.add(*out_n, *ctrl_n, -*gm);
+                }
+
+                Component::Vcvs { out_p, out_n, ctrl_p, ctrl_n, e } => {
+                    let row = branch_row(idx);
+                    mat.add(*out_p, row, 1.0);
+                    mat.add(row, *out_p, 1.0);
+                    mat.add(*out_n, row, -1.0);
+                    mat.add(row, *out_n, -1.0);
+                    mat.add(row, *ctrl_p, -*e);
+                    mat.add(row, *ctrl_n, *e);
+                }
+
+                Component::Cccs { out_p, out_n, controlling, beta } => {
+                    // No own branch row: the controlled current reuses the controlling
+                    // component's existing branch-current column instead of introducing a new one.
+                    // Same rhs-to-matrix sign flip as `Component::Vccs` above.
+                    let ctrl_row = branch_row(*controlling);
+                    mat.add(*out_p, ctrl_row, -*beta);
+                    mat.add(*out_n, ctrl_row, *beta);
+                }
+
+                Component::Ccvs { out_p, out_n, controlling, r } => {
+                    let row = branch_row(idx);
+                    let ctrl_row = branch_row(*controlling);
+                    mat.add(*out_p, row, 1.0);
+                    mat.add(row, *out_p, 1.0);
+                    mat.add(*out_n, row, -1.0);
+                    mat.add(row, *out_n, -1.0);
+                    mat.add(row, ctrl_row, -*r);
+                }
+            }
+        }
+
+        for stamp in extra_stamps {
+            let ExtraStamp { pin1, pin2, g, i_into_pin1 } = *stamp;
+            mat.add(pin1, pin1, g);
+            mat.add(pin2, pin2, g);
+            mat.add(pin1, pin2, -g);
+            mat.add(pin2, pin1, -g);
+            rhs[pin1] += i_into_pin1;
+            rhs[pin2] -= i_into_pin1;
+        }
+
+        // Delete the ground node's row/column, pinning it to 0 V, then solve the reduced system.
+        let keep: Vec<usize> = (0..dim).filter(|&i| i != gnd).collect();
+        let rdim = keep.len();
+        let mut reduced = Matrix::zeros(rdim, rdim);
+        let mut reduced_rhs = vec![0.0; rdim];
+        for (ri, &i) in keep.iter().enumerate() {
+            reduced_rhs[ri] = rhs[i];
+            for (rj, &j) in keep.iter().enumerate() {
+                reduced.set(ri, rj, mat.get(i, j));
+            }
+        }
+
+        let solved = match lu_solve(&mut reduced, &mut reduced_rhs) {
+            Some(x) => x,
+            None => break,
+        };
+
+        let mut x = vec![0.0; dim];
+        for (ri, &i) in keep.iter().enumerate() {
+            x[i] = solved[ri];
+        }
+
+        let max_delta_v = (0..n).map(|i| (x[i] - voltages[i]).abs()).fold(0.0, f64::max);
+        voltages.copy_from_slice(&x[..n]);
+
+        if max_delta_v < tolerance && max_junction_residual < tolerance {
+            break;
+        }
     }
 
     voltages
@@ -0,0 +1,159 @@
+//! Transient analysis.
+//!
+//! Extends the operating-point MNA solver with backward-Euler (or
+//! trapezoidal) companion models for capacitors and inductors, stepping the
+//! circuit forward in time and re-solving the operating point at each step.
+
+use std::collections::HashMap;
+use crate::general::circuit::{Circuit, Component};
+use crate::simulation::op::{newton_mna, ExtraStamp};
+
+/// Selects which companion-model approximation replaces reactive elements
+/// with a conductance and current source at each time step.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum IntegrationMethod {
+    /// `geq = C/h` (capacitor) / `geq = h/L` (inductor). First-order accurate, unconditionally stable.
+    BackwardEuler,
+    /// `geq = 2C/h` (capacitor) / `geq = h/(2L)` (inductor). Second-order accurate.
+    Trapezoidal,
+}
+
+/// Simulates the circuit's transient response from `t = 0` to `t_stop` in
+/// fixed steps of `h`, replacing each [`Component::Capacitor`] and
+/// [`Component::Inductor`] with a Norton companion model — a conductance in
+/// parallel with a current source — that is re-stamped alongside the
+/// resistors and diodes at every step, reusing the same Newton/MNA
+/// machinery as [`crate::simulation::op::simulate_op_mna`].
+///
+/// # Parameters
+/// - `circuit`: The circuit description.
+/// - `t_stop`: The simulation end time.
+/// - `h`: The fixed time step.
+/// - `tolerance`: The Newton-iteration convergence threshold used at every step.
+/// - `initial_conditions`: Optional initial state for reactive elements, keyed by each
+///   component's index in `circuit.components` — the initial capacitor voltage or
+///   inductor current. Elements with no entry start at `0`.
+/// - `method`: The companion-model integration scheme.
+///
+/// # Returns
+/// One node-voltage vector per time step, starting with `t = 0`, each normalized relative to the ground node.
+pub fn simulate_tran(
+    circuit: &Circuit,
+    t_stop: f64,
+    h: f64,
+    tolerance: f64,
+    initial_conditions: Option<HashMap<usize, f64>>,
+    method: IntegrationMethod,
+) -> Vec<Vec<f64>> {
+    let initial_conditions = initial_conditions.unwrap_or_default();
+    let gnd = circuit.ground_node;
+
+    // Per-reactive-element state, indexed the same way as `circuit.components`. Trapezoidal
+    // integration needs both the previous voltage and the previous current for each element
+    // (backward Euler only needs one of the two), so both maps are seeded for both component
+    // kinds: `initial_conditions` supplies the voltage/current the element is defined by, and
+    // the other starts at `0` (the element is at rest).
+    let mut v_prev: HashMap<usize, f64> = HashMap::new();
+    let mut i_prev: HashMap<usize, f64> = HashMap::new();
+    for (idx, component) in circuit.components.iter().enumerate() {
+        match component {
+            Component::Capacitor { .. } => {
+                v_prev.insert(idx, *initial_conditions.get(&idx).unwrap_or(&0.0));
+                i_prev.insert(idx, 0.0);
+            }
+            Component::Inductor { .. } => {
+                i_prev.insert(idx, *initial_conditions.get(&idx).unwrap_or(&0.0));
+                v_prev.insert(idx, 0.0);
+            }
+            _ => {}
+        }
+    }
+
+    // Solve the `t = 0` operating point with each reactive element held at its initial
+    // condition: a capacitor at a fixed voltage is an ideal voltage source (approximated,
+    // like the inductor's DC short in `newton_mna`, with a large companion conductance rather
+    // than a branch equation); an inductor at a fixed current is exactly an ideal current source.
+    // Element currents are taken as flowing from `pin1` through the element to `pin2`.
+    let ic_stamps: Vec<ExtraStamp> = circuit.components.iter().enumerate().filter_map(|(idx, component)| {
+        match component {
+            Component::Capacitor { pin1, pin2, .. } => {
+                let g = 1e9;
+                Some(ExtraStamp { pin1: *pin1, pin2: *pin2, g, i_into_pin1: g * v_prev[&idx] })
+            }
+            Component::Inductor { pin1, pin2, .. } => {
+                Some(ExtraStamp { pin1: *pin1, pin2: *pin2, g: 0.0, i_into_pin1: -i_prev[&idx] })
+            }
+            _ => None,
+        }
+    }).collect();
+
+    let mut voltages = newton_mna(circuit, tolerance, vec![0.0; circuit.nodes_count], &ic_stamps);
+    let mut history = vec![normalize(&voltages, gnd)];
+
+    let steps = (t_stop / h).round() as usize;
+    for _ in 0..steps {
+        let mut extra = Vec::new();
+
+        for (idx, component) in circuit.components.iter().enumerate() {
+            match component {
+                Component::Capacitor { pin1, pin2, c } => {
+                    let vp = v_prev[&idx];
+                    let (geq, ieq) = match method {
+                        IntegrationMethod::BackwardEuler => (c / h, (c / h) * vp),
+                        IntegrationMethod::Trapezoidal => {
+                            let ip = i_prev[&idx];
+                            (2.0 * c / h, (2.0 * c / h) * vp + ip)
+                        }
+                    };
+                    extra.push(ExtraStamp { pin1: *pin1, pin2: *pin2, g: geq, i_into_pin1: ieq });
+                }
+                Component::Inductor { pin1, pin2, l } => {
+                    let ip = i_prev[&idx];
+                    let (geq, ieq) = match method {
+                        IntegrationMethod::BackwardEuler => (h / l, -ip),
+                        IntegrationMethod::Trapezoidal => {
+                            let vp = v_prev[&idx];
+                            (h / (2.0 * l), -(ip + (h / (2.0 * l)) * vp))
+                        }
+                    };
+                    extra.push(ExtraStamp { pin1: *pin1, pin2: *pin2, g: geq, i_into_pin1: ieq });
+                }
+                _ => {}
+            }
+        }
+
+        voltages = newton_mna(circuit, tolerance, voltages, &extra);
+
+        // Advance reactive-element state from the freshly solved node voltages.
+        for (idx, component) in circuit.components.iter().enumerate() {
+            match component {
+                Component::Capacitor { pin1, pin2, .. } => {
+                    let stamp = extra.iter()
+                        .find(|s| s.pin1 == *pin1 && s.pin2 == *pin2)
+                        .expect("capacitor companion stamp was pushed above");
+                    let v_c = voltages[*pin1] - voltages[*pin2];
+                    v_prev.insert(idx, v_c);
+                    i_prev.insert(idx, stamp.g * v_c - stamp.i_into_pin1);
+                }
+                Component::Inductor { pin1, pin2, .. } => {
+                    let stamp = extra.iter()
+                        .find(|s| s.pin1 == *pin1 && s.pin2 == *pin2)
+                        .expect("inductor companion stamp was pushed above");
+                    let v_l = voltages[*pin1] - voltages[*pin2];
+                    i_prev.insert(idx, stamp.g * v_l - stamp.i_into_pin1);
+                    v_prev.insert(idx, v_l);
+                }
+                _ => {}
+            }
+        }
+
+        history.push(normalize(&voltages, gnd));
+    }
+
+    history
+}
+
+fn normalize(voltages: &[f64], ground_node: usize) -> Vec<f64> {
+    let gnd_v = voltages[ground_node];
+    voltages.iter().map(|v| v - gnd_v).collect()
+}